@@ -0,0 +1,61 @@
+//! Support for reading `[build]` overrides out of `.cargo/config.toml`
+
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Deserialize;
+
+use crate::Result;
+
+/// The subset of `.cargo/config.toml` that axoproject cares about
+#[derive(Debug, Default, Clone)]
+pub struct CargoConfigBuild {
+    /// `build.target-dir`, resolved to an absolute path
+    pub target_dir: Option<Utf8PathBuf>,
+    /// `build.target`, the default target triple
+    pub target: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct CargoConfig {
+    #[serde(default)]
+    build: CargoConfigBuildToml,
+}
+
+#[derive(Deserialize, Default)]
+struct CargoConfigBuildToml {
+    #[serde(rename = "target-dir")]
+    target_dir: Option<Utf8PathBuf>,
+    target: Option<String>,
+}
+
+/// Walk up from `workspace_dir` looking for the first `.cargo/config.toml`
+/// (or the legacy extension-less `.cargo/config`), matching Cargo's own
+/// config resolution, and read its `[build]` overrides.
+///
+/// Returns the defaults (nothing set) if no such file is found.
+pub fn find_cargo_config_build(workspace_dir: &Utf8Path) -> Result<CargoConfigBuild> {
+    for dir in workspace_dir.ancestors() {
+        let toml_path = dir.join(".cargo").join("config.toml");
+        let legacy_path = dir.join(".cargo").join("config");
+        let config_path = if toml_path.is_file() {
+            toml_path
+        } else if legacy_path.is_file() {
+            legacy_path
+        } else {
+            continue;
+        };
+
+        let config_src = axoasset::SourceFile::load_local(&config_path)?;
+        let config: CargoConfig = config_src.deserialize_toml()?;
+
+        // Cargo resolves a relative `target-dir` against the directory that
+        // contains the `.cargo` directory, not `.cargo` itself.
+        let target_dir = config.build.target_dir.map(|target_dir| dir.join(target_dir));
+
+        return Ok(CargoConfigBuild {
+            target_dir,
+            target: config.build.target,
+        });
+    }
+
+    Ok(CargoConfigBuild::default())
+}
@@ -22,14 +22,24 @@ impl Manifest {
             None
         }
     }
+
+    fn workspace_packages(&self) -> Vec<Package> {
+        self.workspace
+            .as_ref()
+            .and_then(|workspace| workspace.packages.clone())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Deserialize)]
 struct Workspace {
     members: Option<Vec<String>>,
+    /// Packages defined inline, as an alternative to an on-disk member with
+    /// its own `dist.toml`
+    packages: Option<Vec<Package>>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
 #[serde(rename_all = "kebab-case")]
 struct Package {
     name: String,
@@ -51,6 +61,12 @@ struct Package {
     cdylibs: Vec<String>,
     build_command: Vec<String>,
     version: Option<semver::Version>,
+    /// Glob patterns of files to include, overriding the default walk
+    /// [`crate::list_package_files`] does
+    include: Option<Vec<String>>,
+    /// Glob patterns of files to exclude, overriding the default walk
+    /// [`crate::list_package_files`] does
+    exclude: Option<Vec<String>>,
 }
 
 /// Try to find a generic workspace at the given path
@@ -71,46 +87,80 @@ pub fn get_workspace(start_dir: &Utf8Path, clamp_to_dir: Option<&Utf8Path>) -> W
     }
 }
 
+/// Where a workspace member comes from: an on-disk directory with its own
+/// `dist.toml`, or a package table defined inline in the root `dist.toml`'s
+/// `[[workspace.packages]]`
+enum Member {
+    Path(Utf8PathBuf),
+    Inline(Package),
+}
+
 fn workspace_from(manifest_path: &Utf8Path) -> Result<WorkspaceInfo> {
     let workspace_dir = manifest_path.parent().unwrap().to_path_buf();
 
     let manifest = load_root_dist_toml(manifest_path)?;
-    // If this is a workspace, read its members and map those entries
-    // to expected paths on disk
-    let expected_paths = if let Some(members) = manifest.workspace_members() {
-        members
-            .iter()
-            .map(|name| workspace_dir.join(name))
-            .map(Utf8PathBuf::from)
-            .collect()
-    // If this *isn't* a workspace, the root is the only app
-    } else if manifest.package.is_some() {
-        vec![workspace_dir.to_path_buf()]
-    } else {
-        return Err(AxoprojectError::DistTomlMalformedError {
-            path: manifest_path.to_path_buf(),
-        });
-    };
 
-    workspace_info(manifest_path, &workspace_dir, &expected_paths)
+    let mut members: Vec<Member> = manifest
+        .workspace_packages()
+        .into_iter()
+        .map(Member::Inline)
+        .collect();
+
+    // If this is a workspace, read its on-disk members and map those
+    // entries to expected paths on disk
+    if let Some(member_names) = manifest.workspace_members() {
+        members.extend(
+            member_names
+                .iter()
+                .map(|name| Member::Path(workspace_dir.join(name))),
+        );
+    // If this *isn't* a workspace (and has no inline packages either), the
+    // root is the only app
+    } else if members.is_empty() {
+        if manifest.package.is_some() {
+            members.push(Member::Path(workspace_dir.to_path_buf()));
+        } else {
+            return Err(AxoprojectError::DistTomlMalformedError {
+                path: manifest_path.to_path_buf(),
+            });
+        }
+    }
+
+    workspace_info(manifest_path, &workspace_dir, &members)
 }
 
-fn package_info(manifest_root: &Utf8PathBuf) -> Result<PackageInfo> {
-    let manifest_path = manifest_root.join("dist.toml");
-    let manifest = load_root_dist_toml(&manifest_path)?;
+fn package_info(
+    manifest_path: &Utf8Path,
+    workspace_dir: &Utf8Path,
+    member: &Member,
+) -> Result<PackageInfo> {
+    match member {
+        Member::Path(member_dir) => {
+            let member_manifest_path = member_dir.join("dist.toml");
+            let manifest = load_root_dist_toml(&member_manifest_path)?;
+            let package = manifest.package.ok_or_else(|| AxoprojectError::PackageMissingError {
+                path: member_manifest_path.clone(),
+            })?;
+            build_package_info(package, member_manifest_path, member_dir)
+        }
+        // Inline packages live in the root manifest, and resolve their
+        // relative paths (readme, etc.) against the workspace root.
+        Member::Inline(package) => {
+            build_package_info(package.clone(), manifest_path.to_owned(), workspace_dir)
+        }
+    }
+}
 
-    let package = if let Some(package) = manifest.package {
-        package
-    } else {
-        return Err(AxoprojectError::PackageMissingError {
-            path: manifest_path,
-        });
-    };
+fn build_package_info(
+    package: Package,
+    manifest_path: Utf8PathBuf,
+    package_root: &Utf8Path,
+) -> Result<PackageInfo> {
     let version = package.version.map(Version::Generic);
 
     Ok(PackageInfo {
-        manifest_path: manifest_path.clone(),
-        package_root: manifest_path.clone(),
+        manifest_path,
+        package_root: package_root.to_owned(),
         name: package.name,
         version,
         description: package.description,
@@ -131,6 +181,10 @@ fn package_info(manifest_root: &Utf8PathBuf) -> Result<PackageInfo> {
         cargo_metadata_table: None,
         #[cfg(feature = "cargo-projects")]
         cargo_package_id: None,
+        #[cfg(feature = "cargo-projects")]
+        cargo_include: None,
+        #[cfg(feature = "cargo-projects")]
+        cargo_exclude: None,
         build_command: Some(package.build_command),
     })
 }
@@ -138,13 +192,13 @@ fn package_info(manifest_root: &Utf8PathBuf) -> Result<PackageInfo> {
 fn workspace_info(
     manifest_path: &Utf8Path,
     workspace_dir: &Utf8PathBuf,
-    expected_paths: &[Utf8PathBuf],
+    members: &[Member],
 ) -> Result<WorkspaceInfo> {
     let root_auto_includes = crate::find_auto_includes(workspace_dir)?;
 
-    let package_info = expected_paths
+    let package_info = members
         .iter()
-        .map(package_info)
+        .map(|member| package_info(manifest_path, workspace_dir, member))
         .collect::<Result<Vec<PackageInfo>>>()?;
 
     let repository_url = package_info
@@ -152,14 +206,23 @@ fn workspace_info(
         .map(|p| p.repository_url.to_owned())
         .unwrap_or(None);
 
+    // A `.cargo/config.toml` `[build]` section can override where build
+    // output lands and which target is built by default, even for a
+    // non-cargo project.
+    let cargo_config = crate::cargo_config::find_cargo_config_build(workspace_dir)?;
+    let target_dir = cargo_config
+        .target_dir
+        .unwrap_or_else(|| workspace_dir.join("target"));
+
     Ok(WorkspaceInfo {
         kind: crate::WorkspaceKind::Generic,
-        target_dir: workspace_dir.join("target"),
+        target_dir,
         workspace_dir: workspace_dir.to_owned(),
         package_info,
         manifest_path: manifest_path.to_owned(),
         repository_url,
         root_auto_includes,
+        default_target: cargo_config.target,
         warnings: vec![],
         #[cfg(feature = "cargo-projects")]
         cargo_metadata_table: None,
@@ -174,3 +237,30 @@ fn load_root_dist_toml(manifest_path: &Utf8Path) -> Result<Manifest> {
     let manifest = manifest_src.deserialize_toml()?;
     Ok(manifest)
 }
+
+/// Read the `include`/`exclude` glob overrides for `package_name` out of a
+/// `dist.toml`, for [`crate::list_package_files`]
+///
+/// `package_name` disambiguates between a `[package]` table and the
+/// `[[workspace.packages]]` entries that can share the same manifest file as
+/// inline workspace members.
+pub(crate) fn package_include_exclude(
+    manifest_path: &Utf8Path,
+    package_name: &str,
+) -> Result<(Vec<String>, Vec<String>)> {
+    let manifest = load_root_dist_toml(manifest_path)?;
+
+    let package = manifest
+        .package
+        .filter(|package| package.name == package_name)
+        .or_else(|| {
+            manifest
+                .workspace_packages()
+                .into_iter()
+                .find(|package| package.name == package_name)
+        });
+
+    Ok(package
+        .map(|package| (package.include.unwrap_or_default(), package.exclude.unwrap_or_default()))
+        .unwrap_or_default())
+}
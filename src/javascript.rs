@@ -3,53 +3,89 @@ use std::{fs::File, io::BufReader};
 use camino::{Utf8Path, Utf8PathBuf};
 use guppy::PackageId;
 use miette::{Context, IntoDiagnostic};
-use oro_common::{Manifest, Repository};
+use oro_common::{Manifest, PersonField, Repository};
+use serde::Deserialize;
 
-use crate::{PackageInfo, Result, SortedMap, WorkspaceInfo, WorkspaceKind};
+use crate::{errors::AxoprojectError, PackageInfo, Result, SortedMap, WorkspaceInfo, WorkspaceKind};
 
 pub fn get_project() -> Result<WorkspaceInfo> {
     let root = workspace_root().unwrap();
     let manifest_path = root.join("package.json");
     let manifest = load_manifest(&manifest_path)?;
 
-    // For now this code is fairly naive and doesn't understand workspaces.
-    // We assume the first package.json we find is "the root package" and
-    // has the binary we care about.
-
     // Just assume ./node_modules is the target?
     let target_dir = root.join("node_modules");
 
     let root_auto_includes = crate::find_auto_includes(&root)?;
+    let repository_url = manifest.repository.clone().and_then(repository_to_url);
+    let workspace_globs = workspaces_field(&manifest_path)?;
+
+    let mut package_info = SortedMap::new();
+
+    match (manifest.name.is_some(), workspace_globs.is_some()) {
+        // No name and no `workspaces` field: this isn't a package *or* a
+        // workspace root, there's nothing here for us to build.
+        (false, false) => {
+            return Err(AxoprojectError::PackageJsonMissingNameError { manifest_path });
+        }
+        // Not having a name is common to virtual manifests! A root manifest
+        // with a `workspaces` field but no name only exists to declare the
+        // workspace, so it's used for `target_dir`/auto-includes below but
+        // doesn't ship as a package of its own.
+        (false, true) => {}
+        (true, _) => {
+            let info = package_info_from_manifest(manifest, &root_auto_includes)?;
+            package_info.insert(PackageId::new(info.name.clone()), info);
+        }
+    }
+
+    for glob in workspace_globs.into_iter().flatten() {
+        for member_dir in expand_workspace_glob(&root, &glob) {
+            let member_manifest_path = member_dir.join("package.json");
+            if !member_manifest_path.is_file() {
+                continue;
+            }
+            let member_manifest = load_manifest(&member_manifest_path)?;
+            if member_manifest.name.is_none() {
+                // Can't address a package with no name, skip it.
+                continue;
+            }
+            let member_auto_includes = crate::find_auto_includes(&member_dir)?;
+            let info = package_info_from_manifest(member_manifest, &member_auto_includes)?;
+            package_info.insert(PackageId::new(info.name.clone()), info);
+        }
+    }
+
+    Ok(WorkspaceInfo {
+        kind: WorkspaceKind::Rust,
+        target_dir,
+        workspace_dir: root,
+        package_info,
+        manifest_path,
+        repository_url,
+        root_auto_includes,
+        // npm has no equivalent of `.cargo/config.toml`'s default target.
+        default_target: None,
+    })
+}
 
-    // Not having a name is common to virtual manifests!
+fn package_info_from_manifest(
+    manifest: Manifest,
+    auto_includes: &crate::AutoIncludes,
+) -> Result<PackageInfo> {
     let package_name = manifest
         .name
-        .expect("your package doesn't have a name, is it a workspace? We don't support that yet.");
+        .expect("caller already checked this manifest has a name");
     let version = manifest.version.as_ref().map(|v| v.to_string());
     let authors = manifest
         .author
         .and_then(|a| match a {
-            oro_common::PersonField::Str(s) => Some(vec![s]),
+            PersonField::Str(s) => Some(vec![s]),
             // Not yet implemented!
-            oro_common::PersonField::Obj(_) => None,
+            PersonField::Obj(_) => None,
         })
         .unwrap_or_default();
-
-    let repository_url = manifest.repository.and_then(|url| match url {
-        // TODO: process this into a proper URL?
-        //
-        // It can be things like:
-        //
-        // * "npm/npm"
-        // * "github:user/repo"
-        // * "gist:11081aaa281"
-        // * "bitbucket:user/repo"
-        // * "gitlab:user/repo"
-        //
-        // Using the same syntax as https://docs.npmjs.com/cli/v7/commands/npm-install
-        Repository::Str(repo) => Some(repo),
-        Repository::Obj { url, .. } => url,
-    });
+    let repository_url = manifest.repository.and_then(repository_to_url);
 
     let mut info = PackageInfo {
         name: package_name.clone(),
@@ -59,7 +95,7 @@ pub fn get_project() -> Result<WorkspaceInfo> {
         license: manifest.license,
         // FIXME: is there any JS equivalent to this?
         publish: true,
-        repository_url: repository_url.clone(),
+        repository_url,
         homepage_url: manifest.homepage,
         // FIXME: is there any JS equivalent to this?
         documentation_url: None,
@@ -72,20 +108,125 @@ pub fn get_project() -> Result<WorkspaceInfo> {
         // FIXME: don't just assume this is a binary?
         binaries: vec![package_name.clone()],
     };
-    crate::merge_auto_includes(&mut info, &root_auto_includes);
+    crate::merge_auto_includes(&mut info, auto_includes);
 
-    let mut package_info = SortedMap::new();
-    package_info.insert(PackageId::new(package_name), info);
+    Ok(info)
+}
 
-    Ok(WorkspaceInfo {
-        kind: WorkspaceKind::Rust,
-        target_dir,
-        workspace_dir: root,
-        package_info,
-        manifest_path,
-        repository_url,
-        root_auto_includes,
-    })
+fn repository_to_url(repo: Repository) -> Option<String> {
+    match repo {
+        Repository::Str(repo) => Some(normalize_repository_url(repo)),
+        Repository::Obj { url, .. } => url.map(normalize_repository_url),
+    }
+}
+
+/// Normalize npm's repository shorthands into a URL that's actually
+/// browsable, using the same syntax npm itself accepts:
+/// <https://docs.npmjs.com/cli/v7/commands/npm-install>
+///
+/// Handles bare `user/repo`, the `github:`/`gitlab:`/`bitbucket:`/`gist:`
+/// prefixes, SCP-style `git@github.com:user/repo.git` urls (other SCP-style
+/// hosts are left as they are), and strips any
+/// `git+` prefix or trailing `.git` left over from a full git URL.
+fn normalize_repository_url(repo: String) -> String {
+    let repo = match repo.strip_prefix("git+") {
+        Some(rest) => rest.to_string(),
+        None => repo,
+    };
+
+    let url = if let Some(rest) = repo.strip_prefix("github:") {
+        format!("https://github.com/{rest}")
+    } else if let Some(rest) = repo.strip_prefix("gitlab:") {
+        format!("https://gitlab.com/{rest}")
+    } else if let Some(rest) = repo.strip_prefix("bitbucket:") {
+        format!("https://bitbucket.org/{rest}")
+    } else if let Some(rest) = repo.strip_prefix("gist:") {
+        format!("https://gist.github.com/{rest}")
+    } else if let Some(rest) = repo.strip_prefix("git@github.com:") {
+        format!("https://github.com/{rest}")
+    } else if !repo.contains("://") && !repo.contains('@') && repo.matches('/').count() == 1 {
+        // Bare `user/repo` shorthand, assumed to be GitHub.
+        format!("https://github.com/{repo}")
+    } else {
+        repo
+    };
+
+    match url.strip_suffix(".git") {
+        Some(trimmed) => trimmed.to_string(),
+        None => url,
+    }
+}
+
+/// Expand a single `workspaces` entry (e.g. `"packages/*"`) into the package
+/// directories it refers to, relative to `root`.
+///
+/// Only the glob forms npm itself documents are supported: a plain path, a
+/// trailing `/*` (one level of subdirectories) or a trailing `/**` (any
+/// depth of subdirectories). `node_modules` is never descended into.
+fn expand_workspace_glob(root: &Utf8Path, pattern: &str) -> Vec<Utf8PathBuf> {
+    if let Some(prefix) = pattern.strip_suffix("/**") {
+        let mut dirs = Vec::new();
+        collect_dirs_recursive(&root.join(prefix), &mut dirs);
+        dirs
+    } else if let Some(prefix) = pattern.strip_suffix("/*") {
+        list_subdirs(&root.join(prefix))
+    } else {
+        vec![root.join(pattern)]
+    }
+}
+
+fn list_subdirs(dir: &Utf8Path) -> Vec<Utf8PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| Utf8PathBuf::from_path_buf(entry.path()).ok())
+        .filter(|path| path.is_dir() && path.file_name() != Some("node_modules"))
+        .collect()
+}
+
+fn collect_dirs_recursive(dir: &Utf8Path, out: &mut Vec<Utf8PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let Ok(path) = Utf8PathBuf::from_path_buf(entry.path()) else {
+            continue;
+        };
+        if path.is_dir() && path.file_name() != Some("node_modules") {
+            out.push(path.clone());
+            collect_dirs_recursive(&path, out);
+        }
+    }
+}
+
+/// The `workspaces` field of a root `package.json`, supporting both the
+/// plain array form and npm's `{ "packages": [...] }` object form.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WorkspacesField {
+    List(Vec<String>),
+    Table { packages: Vec<String> },
+}
+
+/// Read just the `workspaces` field out of a `package.json`, if it has one.
+///
+/// `oro_common`'s [`Manifest`] doesn't model workspaces (npm resolves them
+/// out-of-band, orogene doesn't need to know about them), so we go back to
+/// the raw JSON for this one field.
+fn workspaces_field(manifest_path: &Utf8Path) -> Result<Option<Vec<String>>> {
+    let raw = load_raw_manifest(manifest_path)?;
+    let Some(workspaces) = raw.get("workspaces") else {
+        return Ok(None);
+    };
+    let field: WorkspacesField = serde_json::from_value(workspaces.clone())
+        .into_diagnostic()
+        .wrap_err("package.json `workspaces` field is malformed")?;
+    Ok(Some(match field {
+        WorkspacesField::List(globs) => globs,
+        WorkspacesField::Table { packages } => packages,
+    }))
 }
 
 fn workspace_root() -> Option<Utf8PathBuf> {
@@ -111,3 +252,34 @@ fn load_manifest(manifest_path: &Utf8Path) -> Result<Manifest> {
         .wrap_err("failed to parse package.json")?;
     Ok(manifest)
 }
+
+/// Read the `include`/`exclude` glob overrides out of a `package.json`, for
+/// [`crate::list_package_files`]
+///
+/// These aren't fields npm itself understands, they're an axoproject
+/// extension for users who want to override the default file walk.
+pub(crate) fn package_include_exclude(manifest_path: &Utf8Path) -> Result<(Vec<String>, Vec<String>)> {
+    let raw = load_raw_manifest(manifest_path)?;
+    Ok((
+        string_array_field(&raw, "include"),
+        string_array_field(&raw, "exclude"),
+    ))
+}
+
+fn string_array_field(raw: &serde_json::Value, key: &str) -> Vec<String> {
+    raw.get(key)
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default()
+}
+
+fn load_raw_manifest(manifest_path: &Utf8Path) -> Result<serde_json::Value> {
+    let file = File::open(manifest_path)
+        .into_diagnostic()
+        .wrap_err("failed to read package.json")?;
+    let reader = BufReader::new(file);
+    let raw: serde_json::Value = serde_json::from_reader(reader)
+        .into_diagnostic()
+        .wrap_err("failed to parse package.json")?;
+    Ok(raw)
+}
@@ -0,0 +1,10 @@
+//! axoproject: parse and enumerate info about rust/js/generic projects
+
+pub mod cargo_config;
+pub mod errors;
+pub mod generic;
+pub mod javascript;
+pub mod package_files;
+pub mod rust;
+
+pub use package_files::list_package_files;
@@ -0,0 +1,160 @@
+//! Enumerating the files that actually belong to a package, for archives,
+//! checksums, and SBOMs.
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    WalkBuilder,
+};
+use miette::{Context, IntoDiagnostic};
+
+use crate::{PackageInfo, Result};
+
+/// Walk `package`'s root and return the set of files that would ship.
+///
+/// This honors:
+/// * git tracking, when the package lives in a git repo — `.gitignore` and
+///   `.git/info/exclude` are respected via a gitignore-aware walk
+/// * explicit `include`/`exclude` glob overrides from the package's manifest
+///   (`package.include`/`package.exclude` for cargo, the generic and npm
+///   equivalents otherwise), matched the same gitignore-style way cargo
+///   itself matches them
+///
+/// When `include` is set, it's treated the way cargo treats it: as the
+/// authoritative file list, not a filter layered on top of the git-tracked
+/// walk. Otherwise a file named in `include` but hidden by `.gitignore`
+/// would silently miss a package that cargo itself would still ship.
+///
+/// `readme_file`, `license_files`, and `changelog_file` are always included,
+/// even if an `exclude` glob would otherwise drop them. `target/` and
+/// `node_modules/` are never descended into.
+pub fn list_package_files(package: &PackageInfo) -> Result<Vec<Utf8PathBuf>> {
+    let package_root = &package.package_root;
+    let (include, exclude) = package_globs(package)?;
+    let include_matcher = build_matcher(package_root, &include)?;
+    let exclude_matcher = build_matcher(package_root, &exclude)?;
+
+    let mut walker = WalkBuilder::new(package_root);
+    // `ignore` hides dotfiles by default, but git tracks plenty of them
+    // (`.cargo/config.toml`, `.npmrc`, `.env.example`, ...) — only
+    // `.gitignore`/`.git/info/exclude` and our own globs should filter here.
+    walker.hidden(false);
+    if include_matcher.is_some() {
+        // An explicit `include` is authoritative, the same way it is for
+        // cargo: it names files to ship even if `.gitignore` would hide
+        // them, so don't let the git-aware walk drop them first.
+        walker.git_ignore(false);
+        walker.git_exclude(false);
+        walker.git_global(false);
+    }
+    walker.filter_entry(|entry| {
+        let name = entry.file_name().to_string_lossy();
+        // `.git` itself is no longer skipped as hidden, but its internals
+        // are never git-tracked files.
+        name != "target" && name != "node_modules" && name != ".git"
+    });
+
+    let mut files = Vec::new();
+    for entry in walker.build() {
+        let entry = entry.into_diagnostic().wrap_err("failed to walk package files")?;
+        if !entry.file_type().map(|ft| ft.is_file()).unwrap_or(false) {
+            continue;
+        }
+        let Ok(path) = Utf8PathBuf::from_path_buf(entry.into_path()) else {
+            continue;
+        };
+        let Ok(rel_path) = path.strip_prefix(package_root) else {
+            continue;
+        };
+
+        if let Some(matcher) = &include_matcher {
+            if !matcher.matched_path_or_any_parents(rel_path, false).is_ignore() {
+                continue;
+            }
+        }
+        if let Some(matcher) = &exclude_matcher {
+            if matcher.matched_path_or_any_parents(rel_path, false).is_ignore() {
+                continue;
+            }
+        }
+
+        files.push(path);
+    }
+
+    for forced in force_included_files(package) {
+        if forced.is_file() && !files.contains(&forced) {
+            files.push(forced);
+        }
+    }
+
+    Ok(files)
+}
+
+/// Files that always ship regardless of `include`/`exclude`
+fn force_included_files(package: &PackageInfo) -> Vec<Utf8PathBuf> {
+    let resolve = |p: &Utf8Path| -> Utf8PathBuf {
+        if p.is_absolute() {
+            p.to_owned()
+        } else {
+            package.package_root.join(p)
+        }
+    };
+
+    package
+        .readme_file
+        .as_deref()
+        .map(resolve)
+        .into_iter()
+        .chain(package.changelog_file.as_deref().map(resolve))
+        .chain(package.license_files.iter().map(|p| resolve(p)))
+        .collect()
+}
+
+fn package_globs(package: &PackageInfo) -> Result<(Vec<String>, Vec<String>)> {
+    match package.manifest_path.file_name() {
+        Some("Cargo.toml") => cargo_package_globs(package),
+        Some("dist.toml") => {
+            crate::generic::package_include_exclude(&package.manifest_path, &package.name)
+        }
+        Some("package.json") => crate::javascript::package_include_exclude(&package.manifest_path),
+        _ => Ok((vec![], vec![])),
+    }
+}
+
+#[cfg(feature = "cargo-projects")]
+fn cargo_package_globs(package: &PackageInfo) -> Result<(Vec<String>, Vec<String>)> {
+    // `rust.rs` already had the `cargo_metadata::Package` in hand when it
+    // built this `PackageInfo`, and stashed `include`/`exclude` on it then —
+    // re-running `cargo metadata` here would mean a full workspace
+    // resolution per package just to read two fields we already have.
+    Ok((
+        package.cargo_include.clone().unwrap_or_default(),
+        package.cargo_exclude.clone().unwrap_or_default(),
+    ))
+}
+
+#[cfg(not(feature = "cargo-projects"))]
+fn cargo_package_globs(_package: &PackageInfo) -> Result<(Vec<String>, Vec<String>)> {
+    Ok((vec![], vec![]))
+}
+
+/// Build a gitignore-style matcher out of `patterns`, anchored at `root`.
+///
+/// Cargo's `package.include`/`package.exclude` (and the npm/generic
+/// equivalents) are documented as gitignore-style patterns, not plain globs —
+/// anchoring and `/`-handling differ enough that a `globset::Glob` match
+/// wouldn't agree with cargo's own on patterns like `src/` or `/Cargo.lock`.
+fn build_matcher(root: &Utf8Path, patterns: &[String]) -> Result<Option<Gitignore>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GitignoreBuilder::new(root);
+    for pattern in patterns {
+        builder
+            .add_line(None, pattern)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("invalid glob pattern `{pattern}`"))?;
+    }
+    let matcher = builder.build().into_diagnostic()?;
+    Ok(Some(matcher))
+}
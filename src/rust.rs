@@ -0,0 +1,124 @@
+//! Support for cargo/rust projects
+
+use camino::Utf8Path;
+use miette::{Context, IntoDiagnostic};
+
+use crate::{PackageInfo, Result, Version, WorkspaceInfo, WorkspaceKind, WorkspaceSearch};
+
+/// Placeholder for per-profile `[profile.*]` settings
+///
+/// axoproject doesn't parse `[profile.*]` yet, so this is always empty; it
+/// exists so `WorkspaceInfo::cargo_profiles` has something to hold once that
+/// lands.
+#[derive(Debug, Default, Clone)]
+pub struct CargoProfiles;
+
+impl CargoProfiles {
+    /// An empty set of profiles
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Try to find a cargo workspace at the given path
+///
+/// See [`crate::get_workspaces`][] for the semantics.
+pub fn get_workspace(start_dir: &Utf8Path, clamp_to_dir: Option<&Utf8Path>) -> WorkspaceSearch {
+    let manifest_path = match crate::find_file("Cargo.toml", start_dir, clamp_to_dir) {
+        Ok(path) => path,
+        Err(e) => return WorkspaceSearch::Missing(e),
+    };
+
+    match workspace_from(&manifest_path) {
+        Ok(info) => WorkspaceSearch::Found(info),
+        Err(e) => WorkspaceSearch::Broken {
+            manifest_path,
+            cause: e,
+        },
+    }
+}
+
+fn workspace_from(manifest_path: &Utf8Path) -> Result<WorkspaceInfo> {
+    let metadata = cargo_metadata::MetadataCommand::new()
+        .manifest_path(manifest_path)
+        .exec()
+        .into_diagnostic()
+        .wrap_err("failed to run `cargo metadata`")?;
+
+    let workspace_dir = metadata.workspace_root.clone();
+    let root_auto_includes = crate::find_auto_includes(&workspace_dir)?;
+
+    let package_info = metadata
+        .workspace_members
+        .iter()
+        .filter_map(|id| metadata.packages.iter().find(|p| &p.id == id))
+        .map(package_info)
+        .collect::<Vec<_>>();
+
+    let repository_url = package_info
+        .first()
+        .map(|p: &PackageInfo| p.repository_url.to_owned())
+        .unwrap_or(None);
+
+    // A `.cargo/config.toml` `[build]` section can override where build
+    // output lands and which target is built by default.
+    let cargo_config = crate::cargo_config::find_cargo_config_build(&workspace_dir)?;
+    let target_dir = cargo_config
+        .target_dir
+        .unwrap_or_else(|| metadata.target_directory.clone());
+
+    Ok(WorkspaceInfo {
+        kind: WorkspaceKind::Rust,
+        target_dir,
+        workspace_dir,
+        package_info,
+        manifest_path: manifest_path.to_owned(),
+        repository_url,
+        root_auto_includes,
+        default_target: cargo_config.target,
+        warnings: vec![],
+        #[cfg(feature = "cargo-projects")]
+        cargo_metadata_table: None,
+        #[cfg(feature = "cargo-projects")]
+        cargo_profiles: CargoProfiles::new(),
+    })
+}
+
+fn package_info(package: &cargo_metadata::Package) -> PackageInfo {
+    PackageInfo {
+        manifest_path: package.manifest_path.clone(),
+        package_root: package
+            .manifest_path
+            .parent()
+            .map(|p| p.to_owned())
+            .unwrap_or_else(|| package.manifest_path.clone()),
+        name: package.name.clone(),
+        version: Some(Version::Cargo(package.version.clone())),
+        description: package.description.clone(),
+        authors: package.authors.clone(),
+        license: package.license.clone(),
+        publish: package.publish.is_none(),
+        keywords: Some(package.keywords.clone()),
+        repository_url: package.repository.clone(),
+        homepage_url: package.homepage.clone(),
+        documentation_url: package.documentation.clone(),
+        readme_file: package.readme.clone(),
+        license_files: package.license_file.clone().into_iter().collect(),
+        changelog_file: None,
+        binaries: vec![],
+        cstaticlibs: vec![],
+        cdylibs: vec![],
+        #[cfg(feature = "cargo-projects")]
+        cargo_metadata_table: Some(package.metadata.clone()),
+        #[cfg(feature = "cargo-projects")]
+        cargo_package_id: Some(package.id.clone()),
+        // Recorded here (rather than re-running `cargo metadata` later) so
+        // `list_package_files` doesn't have to resolve the whole workspace
+        // again just to read these two fields.
+        #[cfg(feature = "cargo-projects")]
+        cargo_include: Some(package.include.clone()),
+        #[cfg(feature = "cargo-projects")]
+        cargo_exclude: Some(package.exclude.clone()),
+        build_command: None,
+    }
+}